@@ -32,15 +32,19 @@
 //! }
 //! 
 
-use std::collections::HashMap;
+use std::borrow::Borrow;
+use std::collections::{HashMap, TryReserveError};
 use std::vec::Vec;
 use std::hash::{Hash, BuildHasher, RandomState};
+use std::iter::Chain;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
 
 use std::fmt;
 
 use rand::Rng;
 
-
+mod weighted;
+pub use weighted::WeightedRandSet;
 
 #[derive(Debug, Clone, Default)]
 pub struct RandSet<T, S = RandomState>
@@ -70,14 +74,64 @@ where
         if self.items_vector.is_empty() {
             return None;
         }
-        
+
         let mut rng = rand::rng();
         let random_index = rng.random_range(0..self.items_vector.len());
         Some(&self.items_vector[random_index])
     }
 
-   
-    pub fn get(&self, value: &T) -> Option<&T> {
+    /// Removes and returns a uniformly random element in O(1), via the same
+    /// swap-with-last-slot path as [`RandSet::remove`].
+    pub fn pop_random(&mut self) -> Option<T> {
+        if self.items_vector.is_empty() {
+            return None;
+        }
+
+        let mut rng = rand::rng();
+        let random_index = rng.random_range(0..self.items_vector.len());
+        let value = self.items_vector[random_index].clone();
+        self.remove(&value);
+        Some(value)
+    }
+
+    /// Returns `n` distinct elements chosen uniformly at random, without
+    /// replacement and without mutating the set, using a seeded `rng`.
+    ///
+    /// Implemented via partial Fisher-Yates: indices `0..len` are shuffled
+    /// just enough to pick the first `n`, so the cost is O(min(n, len))
+    /// rather than O(len).
+    pub fn sample_with(&self, n: usize, rng: &mut impl Rng) -> Vec<&T> {
+        let len = self.items_vector.len();
+        let count = n.min(len);
+        // Sparse view of the shuffled index array: only swapped positions
+        // are stored, any other position `k` still holds `k`.
+        let mut swapped: HashMap<usize, usize> = HashMap::with_capacity(count);
+        let mut sampled = Vec::with_capacity(count);
+
+        for i in 0..count {
+            let j = rng.random_range(i..len);
+            let at_i = swapped.get(&i).copied().unwrap_or(i);
+            let at_j = swapped.get(&j).copied().unwrap_or(j);
+            swapped.insert(j, at_i);
+            sampled.push(&self.items_vector[at_j]);
+        }
+
+        sampled
+    }
+
+    /// Returns `n` distinct elements chosen uniformly at random, without
+    /// replacement and without mutating the set. See [`RandSet::sample_with`]
+    /// for a version that takes a seeded `rng`.
+    pub fn sample(&self, n: usize) -> Vec<&T> {
+        self.sample_with(n, &mut rand::rng())
+    }
+
+
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         if self.items_vector.is_empty() {
             return None;
         }
@@ -111,7 +165,34 @@ where
 
 
     pub fn capacity(&self) -> usize{
-        return self.values_to_index.capacity(); 
+        return self.values_to_index.capacity();
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        self.values_to_index.reserve(additional);
+        self.items_vector.reserve(additional);
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements,
+    /// returning an error if either the map or the vector fails to
+    /// allocate.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.values_to_index.try_reserve(additional)?;
+        self.items_vector.try_reserve(additional)?;
+        Ok(())
+    }
+
+    /// Shrinks the capacity of the set as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.values_to_index.shrink_to_fit();
+        self.items_vector.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of the set down to at least `min_capacity`.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.values_to_index.shrink_to(min_capacity);
+        self.items_vector.shrink_to(min_capacity);
     }
 
     pub fn is_empty(&self) -> bool{
@@ -120,14 +201,72 @@ where
 
     pub fn clear(&mut self) {
         self.values_to_index.clear();
-        self.items_vector.clear(); 
+        self.items_vector.clear();
+    }
+
+    /// Retains only the elements for which `f` returns `true`, removing the
+    /// rest via the same swap-remove path as [`RandSet::remove`] so indices
+    /// stay valid throughout.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut i = 0;
+        while i < self.items_vector.len() {
+            if f(&self.items_vector[i]) {
+                i += 1;
+            } else {
+                let value = self.items_vector[i].clone();
+                self.remove(&value);
+            }
+        }
+    }
+
+    /// Removes all elements from the set, returning them as an iterator.
+    ///
+    /// The set is empty after this call, even if the iterator is dropped
+    /// before being fully consumed.
+    pub fn drain(&mut self) -> Drain<T> {
+        self.values_to_index.clear();
+        Drain {
+            iter: self.items_vector.drain(..),
+        }
     }
 
-    pub fn remove(&mut self, value: &T) -> bool{  
+    /// Removes and yields the elements matching `pred`, in arbitrary order.
+    ///
+    /// Elements for which `pred` returns `false` are left in the set,
+    /// unaffected. If the returned iterator is dropped before being fully
+    /// consumed, the remaining matching elements are left in the set.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<T, S, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        ExtractIf {
+            set: self,
+            pred,
+            index: 0,
+        }
+    }
 
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Removes and returns the value in the set, if any, that is equal to
+    /// `value`.
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         let matching_index =  match self.values_to_index.get(value) {
             Some(val) => val.clone(),
-            None => return false,
+            None => return None,
         };
 
         let last_index = self.items_vector.len() - 1;
@@ -137,12 +276,73 @@ where
 
             let swapped_value = &self.items_vector[matching_index];
             self.values_to_index.insert(swapped_value.clone(), matching_index);
-        } 
+        }
 
-        self.items_vector.pop();
+        let removed = self.items_vector.pop();
         self.values_to_index.remove(value);
 
-        true
+        removed
+    }
+
+    /// Replaces and returns the value in the set, if any, that is equal to
+    /// `value`; otherwise inserts `value` and returns `None`.
+    ///
+    /// This is useful when `T` carries data beyond what its `Hash`/`Eq`
+    /// impls inspect, since it lets callers update that extra data while
+    /// keeping the set's identity for `value`.
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        match self.values_to_index.get(&value) {
+            Some(&index) => {
+                let old = std::mem::replace(&mut self.items_vector[index], value.clone());
+                self.values_to_index.remove(&old);
+                self.values_to_index.insert(value, index);
+                Some(old)
+            }
+            None => {
+                self.values_to_index.insert(value.clone(), self.items_vector.len());
+                self.items_vector.push(value);
+                None
+            }
+        }
+    }
+
+    /// Inserts `value` if it isn't already present, then returns a
+    /// reference to the value in the set equal to `value`.
+    pub fn get_or_insert(&mut self, value: T) -> &T {
+        let index = match self.values_to_index.get(&value) {
+            Some(&index) => index,
+            None => {
+                let index = self.items_vector.len();
+                self.values_to_index.insert(value.clone(), index);
+                self.items_vector.push(value);
+                index
+            }
+        };
+
+        &self.items_vector[index]
+    }
+
+    /// Inserts a value computed from `f` into the set if `value` isn't
+    /// already present, then returns a reference to the value in the set
+    /// equal to `value`.
+    pub fn get_or_insert_with<Q, F>(&mut self, value: &Q, f: F) -> &T
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce(&Q) -> T,
+    {
+        let index = match self.values_to_index.get(value) {
+            Some(&index) => index,
+            None => {
+                let owned = f(value);
+                let index = self.items_vector.len();
+                self.values_to_index.insert(owned.clone(), index);
+                self.items_vector.push(owned);
+                index
+            }
+        };
+
+        &self.items_vector[index]
     }
 
     pub fn len(&self) -> usize {
@@ -162,7 +362,11 @@ where
     }
 
 
-    pub fn contains(&self, value: &T) -> bool{  
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
 
         self.values_to_index.contains_key(value)
     }
@@ -170,6 +374,245 @@ where
     pub fn iter(&self) -> std::slice::Iter<T> {
         self.items_vector.iter()
     }
+
+    /// Visit the values representing the intersection, i.e. the values that
+    /// are both in `self` and `other`, without allocating a new set.
+    pub fn intersection<'a>(&'a self, other: &'a RandSet<T, S>) -> Intersection<'a, T, S> {
+        Intersection {
+            iter: self.items_vector.iter(),
+            other,
+        }
+    }
+
+    /// Visit the values representing the difference, i.e. the values that
+    /// are in `self` but not in `other`, without allocating a new set.
+    pub fn difference<'a>(&'a self, other: &'a RandSet<T, S>) -> Difference<'a, T, S> {
+        Difference {
+            iter: self.items_vector.iter(),
+            other,
+        }
+    }
+
+    /// Visit the values representing the union, i.e. all the values in
+    /// `self` or `other`, without allocating a new set.
+    pub fn union<'a>(&'a self, other: &'a RandSet<T, S>) -> Union<'a, T, S> {
+        Union {
+            iter: self.items_vector.iter().chain(other.difference(self)),
+        }
+    }
+
+    /// Visit the values representing the symmetric difference, i.e. the
+    /// values that are in `self` or `other` but not in both, without
+    /// allocating a new set.
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a RandSet<T, S>,
+    ) -> SymmetricDifference<'a, T, S> {
+        SymmetricDifference {
+            iter: self.difference(other).chain(other.difference(self)),
+        }
+    }
+}
+
+/// A lazy iterator over the values representing the intersection of two
+/// `RandSet`s, created by [`RandSet::intersection`].
+pub struct Intersection<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    iter: std::slice::Iter<'a, T>,
+    other: &'a RandSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Intersection<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let elt = self.iter.next()?;
+            if self.other.contains(elt) {
+                return Some(elt);
+            }
+        }
+    }
+}
+
+/// A lazy iterator over the values representing the difference of two
+/// `RandSet`s, created by [`RandSet::difference`].
+pub struct Difference<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    iter: std::slice::Iter<'a, T>,
+    other: &'a RandSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Difference<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            let elt = self.iter.next()?;
+            if !self.other.contains(elt) {
+                return Some(elt);
+            }
+        }
+    }
+}
+
+/// A lazy iterator over the values representing the union of two
+/// `RandSet`s, created by [`RandSet::union`].
+pub struct Union<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    iter: Chain<std::slice::Iter<'a, T>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for Union<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+}
+
+/// A lazy iterator over the values representing the symmetric difference of
+/// two `RandSet`s, created by [`RandSet::symmetric_difference`].
+pub struct SymmetricDifference<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    iter: Chain<Difference<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for SymmetricDifference<'a, T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+}
+
+impl<T, S> BitAnd<&RandSet<T, S>> for &RandSet<T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = RandSet<T, S>;
+
+    /// Returns the intersection of `self` and `rhs` as a new `RandSet`.
+    fn bitand(self, rhs: &RandSet<T, S>) -> RandSet<T, S> {
+        self.intersection(rhs).cloned().collect()
+    }
+}
+
+impl<T, S> BitOr<&RandSet<T, S>> for &RandSet<T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = RandSet<T, S>;
+
+    /// Returns the union of `self` and `rhs` as a new `RandSet`.
+    fn bitor(self, rhs: &RandSet<T, S>) -> RandSet<T, S> {
+        self.union(rhs).cloned().collect()
+    }
+}
+
+impl<T, S> BitXor<&RandSet<T, S>> for &RandSet<T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = RandSet<T, S>;
+
+    /// Returns the symmetric difference of `self` and `rhs` as a new `RandSet`.
+    fn bitxor(self, rhs: &RandSet<T, S>) -> RandSet<T, S> {
+        self.symmetric_difference(rhs).cloned().collect()
+    }
+}
+
+impl<T, S> Sub<&RandSet<T, S>> for &RandSet<T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    type Output = RandSet<T, S>;
+
+    /// Returns the difference of `self` and `rhs` as a new `RandSet`.
+    fn sub(self, rhs: &RandSet<T, S>) -> RandSet<T, S> {
+        self.difference(rhs).cloned().collect()
+    }
+}
+
+/// A draining iterator over the elements of a `RandSet`, created by
+/// [`RandSet::drain`].
+pub struct Drain<'a, T> {
+    iter: std::vec::Drain<'a, T>,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+}
+
+/// An iterator that removes and yields elements matching a predicate,
+/// created by [`RandSet::extract_if`].
+pub struct ExtractIf<'a, T, S, F>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+    F: FnMut(&T) -> bool,
+{
+    set: &'a mut RandSet<T, S>,
+    pred: F,
+    index: usize,
+}
+
+impl<'a, T, S, F> Iterator for ExtractIf<'a, T, S, F>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.index < self.set.items_vector.len() {
+            if (self.pred)(&self.set.items_vector[self.index]) {
+                let value = self.set.items_vector[self.index].clone();
+                self.set.remove(&value);
+                return Some(value);
+            } else {
+                self.index += 1;
+            }
+        }
+        None
+    }
 }
 
 impl<T> RandSet<T, RandomState>
@@ -368,4 +811,226 @@ mod tests {
         assert!(rs != equal_rs);
 
     }
+
+    #[test]
+    fn union() {
+        let a: RandSet<i32> = [1, 2, 3].into_iter().collect();
+        let b: RandSet<i32> = [2, 3, 4].into_iter().collect();
+
+        let mut result: Vec<i32> = a.union(&b).cloned().collect();
+        result.sort();
+        assert_eq!(result, vec![1, 2, 3, 4]);
+
+        let mut via_op: Vec<i32> = (&a | &b).into_iter().collect();
+        via_op.sort();
+        assert_eq!(via_op, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn intersection() {
+        let a: RandSet<i32> = [1, 2, 3].into_iter().collect();
+        let b: RandSet<i32> = [2, 3, 4].into_iter().collect();
+
+        let mut result: Vec<i32> = a.intersection(&b).cloned().collect();
+        result.sort();
+        assert_eq!(result, vec![2, 3]);
+
+        let mut via_op: Vec<i32> = (&a & &b).into_iter().collect();
+        via_op.sort();
+        assert_eq!(via_op, vec![2, 3]);
+    }
+
+    #[test]
+    fn difference() {
+        let a: RandSet<i32> = [1, 2, 3].into_iter().collect();
+        let b: RandSet<i32> = [2, 3, 4].into_iter().collect();
+
+        let mut result: Vec<i32> = a.difference(&b).cloned().collect();
+        result.sort();
+        assert_eq!(result, vec![1]);
+
+        let mut via_op: Vec<i32> = (&a - &b).into_iter().collect();
+        via_op.sort();
+        assert_eq!(via_op, vec![1]);
+    }
+
+    #[test]
+    fn borrowed_lookup_str() {
+        let mut rs = RandSet::<String>::new();
+
+        rs.insert("hello".to_string());
+        rs.insert("world".to_string());
+
+        assert!(rs.contains("hello"));
+        assert_eq!(rs.get("hello"), Some(&"hello".to_string()));
+        assert!(!rs.contains("missing"));
+
+        assert!(rs.remove("hello"));
+        assert!(!rs.contains("hello"));
+        assert_eq!(rs.len(), 1);
+    }
+
+    #[test]
+    fn borrowed_lookup_slice() {
+        let mut rs = RandSet::<Vec<u8>>::new();
+
+        rs.insert(vec![1, 2, 3]);
+
+        assert!(rs.contains(&[1, 2, 3][..]));
+        assert!(rs.remove(&[1, 2, 3][..]));
+        assert!(rs.is_empty());
+    }
+
+    #[test]
+    fn take() {
+        let mut rs = RandSet::<i32>::new();
+        rs.insert(23);
+        rs.insert(40);
+
+        assert_eq!(rs.take(&23), Some(23));
+        assert_eq!(rs.take(&23), None);
+        assert_eq!(rs.len(), 1);
+        assert!(rs.contains(&40));
+    }
+
+    #[test]
+    fn replace() {
+        let mut rs = RandSet::<i32>::new();
+
+        assert_eq!(rs.replace(23), None);
+        assert_eq!(rs.replace(23), Some(23));
+        assert_eq!(rs.len(), 1);
+    }
+
+    #[test]
+    fn get_or_insert() {
+        let mut rs = RandSet::<i32>::new();
+
+        assert_eq!(*rs.get_or_insert(23), 23);
+        assert_eq!(*rs.get_or_insert(23), 23);
+        assert_eq!(rs.len(), 1);
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut rs = RandSet::<String>::new();
+
+        assert_eq!(rs.get_or_insert_with("hello", |s| s.to_string()), "hello");
+        assert_eq!(rs.len(), 1);
+        assert_eq!(rs.get_or_insert_with("hello", |s| s.to_string()), "hello");
+        assert_eq!(rs.len(), 1);
+    }
+
+    #[test]
+    fn retain() {
+        let mut rs: RandSet<i32> = [1, 2, 3, 4, 5].into_iter().collect();
+
+        rs.retain(|&v| v % 2 == 0);
+
+        let mut remaining: Vec<i32> = rs.iter().cloned().collect();
+        remaining.sort();
+        assert_eq!(remaining, vec![2, 4]);
+        assert_eq!(rs.len(), 2);
+    }
+
+    #[test]
+    fn drain() {
+        let mut rs: RandSet<i32> = [1, 2, 3].into_iter().collect();
+
+        let mut drained: Vec<i32> = rs.drain().collect();
+        drained.sort();
+
+        assert_eq!(drained, vec![1, 2, 3]);
+        assert!(rs.is_empty());
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut rs: RandSet<i32> = [1, 2, 3, 4, 5].into_iter().collect();
+
+        let mut extracted: Vec<i32> = rs.extract_if(|&v| v % 2 == 0).collect();
+        extracted.sort();
+
+        assert_eq!(extracted, vec![2, 4]);
+        assert_eq!(rs.len(), 3);
+        assert!(rs.contains(&1));
+        assert!(rs.contains(&3));
+        assert!(rs.contains(&5));
+    }
+
+    #[test]
+    fn reserve_and_shrink() {
+        let mut rs = RandSet::<i32>::new();
+
+        rs.reserve(10);
+        assert!(rs.capacity() >= 10);
+
+        rs.insert(1);
+        rs.insert(2);
+        rs.shrink_to_fit();
+        assert!(rs.capacity() >= rs.len());
+
+        assert!(rs.try_reserve(4).is_ok());
+    }
+
+    #[test]
+    fn pop_random() {
+        let mut rs = RandSet::<i32>::new();
+        assert_eq!(rs.pop_random(), None);
+
+        rs.insert(23);
+        rs.insert(40);
+
+        let popped = rs.pop_random().unwrap();
+        assert!(popped == 23 || popped == 40);
+        assert_eq!(rs.len(), 1);
+        assert!(!rs.contains(&popped));
+    }
+
+    #[test]
+    fn sample() {
+        let rs: RandSet<i32> = [1, 2, 3, 4, 5].into_iter().collect();
+
+        let sampled = rs.sample(3);
+        assert_eq!(sampled.len(), 3);
+
+        let mut unique: Vec<i32> = sampled.into_iter().cloned().collect();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 3);
+
+        // Sampling more than len() caps at len() and doesn't mutate the set.
+        assert_eq!(rs.sample(10).len(), 5);
+        assert_eq!(rs.len(), 5);
+    }
+
+    #[test]
+    fn sample_with_seeded_rng() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let rs: RandSet<i32> = [1, 2, 3, 4, 5].into_iter().collect();
+
+        let mut rng_a = StdRng::seed_from_u64(42);
+        let mut rng_b = StdRng::seed_from_u64(42);
+
+        let sample_a: Vec<i32> = rs.sample_with(3, &mut rng_a).into_iter().cloned().collect();
+        let sample_b: Vec<i32> = rs.sample_with(3, &mut rng_b).into_iter().cloned().collect();
+
+        assert_eq!(sample_a, sample_b);
+    }
+
+    #[test]
+    fn symmetric_difference() {
+        let a: RandSet<i32> = [1, 2, 3].into_iter().collect();
+        let b: RandSet<i32> = [2, 3, 4].into_iter().collect();
+
+        let mut result: Vec<i32> = a.symmetric_difference(&b).cloned().collect();
+        result.sort();
+        assert_eq!(result, vec![1, 4]);
+
+        let mut via_op: Vec<i32> = (&a ^ &b).into_iter().collect();
+        via_op.sort();
+        assert_eq!(via_op, vec![1, 4]);
+    }
 }
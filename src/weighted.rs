@@ -0,0 +1,275 @@
+//! A weighted variant of [`RandSet`](crate::RandSet) that draws elements
+//! with probability proportional to a per-element weight, in O(1) per draw,
+//! using Vose's alias method.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, RandomState};
+
+use rand::Rng;
+
+/// A set that supports O(1) weighted random draws via the alias method.
+///
+/// Each element carries an `f64` weight; [`WeightedRandSet::get_rand_weighted`]
+/// returns elements with probability proportional to their weight. The
+/// alias tables are rebuilt lazily on the next weighted draw after a
+/// mutation, so `insert`/`remove`/`contains` pay no extra cost.
+#[derive(Debug, Clone)]
+pub struct WeightedRandSet<T, S = RandomState>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    values_to_index: HashMap<T, usize, S>,
+    items_vector: Vec<T>,
+    weights: Vec<f64>,
+    alias: Option<AliasTable>,
+}
+
+#[derive(Debug, Clone)]
+struct AliasTable {
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Builds the alias table for `weights` via Vose's alias method.
+    /// Returns `None` if there are no weights, or if they sum to zero or less.
+    fn build(weights: &[f64]) -> Option<Self> {
+        let n = weights.len();
+        if n == 0 {
+            return None;
+        }
+
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut prob = vec![0.0; n];
+        let mut alias = vec![0usize; n];
+
+        let mut small: Vec<usize> = Vec::new();
+        let mut large: Vec<usize> = Vec::new();
+
+        for (i, &p) in scaled.iter().enumerate() {
+            if p < 1.0 {
+                small.push(i);
+            } else {
+                large.push(i);
+            }
+        }
+
+        while !small.is_empty() && !large.is_empty() {
+            let s = small.pop().unwrap();
+            let l = large.pop().unwrap();
+
+            prob[s] = scaled[s];
+            alias[s] = l;
+
+            scaled[l] -= 1.0 - scaled[s];
+            if scaled[l] < 1.0 {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+
+        // Leftover indices have a scaled probability of 1 (exactly, or up to
+        // floating-point rounding); they are certain to be drawn on their
+        // own bucket.
+        for i in large.into_iter().chain(small) {
+            prob[i] = 1.0;
+        }
+
+        Some(AliasTable { prob, alias })
+    }
+
+    fn draw(&self, rng: &mut impl Rng) -> usize {
+        let n = self.prob.len();
+        let i = rng.random_range(0..n);
+        let r: f64 = rng.random();
+        if r < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
+
+impl<T> WeightedRandSet<T, RandomState>
+where
+    T: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        WeightedRandSet {
+            values_to_index: HashMap::new(),
+            items_vector: Vec::new(),
+            weights: Vec::new(),
+            alias: None,
+        }
+    }
+}
+
+impl<T> Default for WeightedRandSet<T, RandomState>
+where
+    T: Hash + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S> WeightedRandSet<T, S>
+where
+    T: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        WeightedRandSet {
+            values_to_index: HashMap::with_hasher(hash_builder),
+            items_vector: Vec::new(),
+            weights: Vec::new(),
+            alias: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items_vector.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items_vector.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.values_to_index.contains_key(value)
+    }
+
+    /// Inserts `value` with the given `weight`. Returns `false` (and leaves
+    /// the set unchanged) if `value` was already present.
+    pub fn insert(&mut self, value: T, weight: f64) -> bool {
+        if self.values_to_index.contains_key(&value) {
+            return false;
+        }
+
+        self.values_to_index
+            .insert(value.clone(), self.items_vector.len());
+        self.items_vector.push(value);
+        self.weights.push(weight);
+        self.alias = None;
+        true
+    }
+
+    /// Removes `value` from the set, using the same swap-with-last-slot
+    /// path as `RandSet::remove` to keep `weights` in lockstep with
+    /// `items_vector`.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let matching_index = match self.values_to_index.get(value) {
+            Some(&index) => index,
+            None => return false,
+        };
+
+        let last_index = self.items_vector.len() - 1;
+
+        if matching_index != last_index {
+            self.items_vector.swap(matching_index, last_index);
+            self.weights.swap(matching_index, last_index);
+
+            let swapped_value = &self.items_vector[matching_index];
+            self.values_to_index
+                .insert(swapped_value.clone(), matching_index);
+        }
+
+        self.items_vector.pop();
+        self.weights.pop();
+        self.values_to_index.remove(value);
+        self.alias = None;
+
+        true
+    }
+
+    /// Draws a random element with probability proportional to its weight.
+    ///
+    /// Rebuilds the alias tables first if they were invalidated by an
+    /// `insert`/`remove` since the last draw.
+    pub fn get_rand_weighted(&mut self) -> Option<&T> {
+        if self.items_vector.is_empty() {
+            return None;
+        }
+
+        if self.alias.is_none() {
+            self.alias = AliasTable::build(&self.weights);
+        }
+
+        let mut rng = rand::rng();
+        let index = match &self.alias {
+            Some(table) => table.draw(&mut rng),
+            None => rng.random_range(0..self.items_vector.len()),
+        };
+
+        Some(&self.items_vector[index])
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<T> {
+        self.items_vector.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_contains() {
+        let mut wrs = WeightedRandSet::<i32>::new();
+
+        assert!(wrs.insert(1, 1.0));
+        assert!(wrs.insert(2, 3.0));
+        assert!(!wrs.insert(1, 5.0));
+
+        assert!(wrs.contains(&1));
+        assert!(wrs.contains(&2));
+        assert_eq!(wrs.len(), 2);
+    }
+
+    #[test]
+    fn remove_keeps_weights_in_lockstep() {
+        let mut wrs = WeightedRandSet::<i32>::new();
+        wrs.insert(1, 1.0);
+        wrs.insert(2, 2.0);
+        wrs.insert(3, 3.0);
+
+        assert!(wrs.remove(&1));
+        assert!(!wrs.contains(&1));
+        assert_eq!(wrs.len(), 2);
+
+        // Every draw must be one of the remaining elements.
+        for _ in 0..20 {
+            let drawn = *wrs.get_rand_weighted().unwrap();
+            assert!(drawn == 2 || drawn == 3);
+        }
+    }
+
+    #[test]
+    fn get_rand_weighted_favors_heavier_weights() {
+        let mut wrs = WeightedRandSet::<i32>::new();
+        wrs.insert(1, 1.0);
+        wrs.insert(2, 99.0);
+
+        let mut heavy_draws = 0;
+        for _ in 0..200 {
+            if *wrs.get_rand_weighted().unwrap() == 2 {
+                heavy_draws += 1;
+            }
+        }
+
+        assert!(heavy_draws > 150);
+    }
+
+    #[test]
+    fn empty_set_returns_none() {
+        let mut wrs = WeightedRandSet::<i32>::new();
+        assert_eq!(wrs.get_rand_weighted(), None);
+    }
+}